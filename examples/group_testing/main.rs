@@ -1,10 +1,11 @@
-use poise::{serenity_prelude as serenity, Command, CommandGroup};
-use std::{env::var, sync::Arc, time::Duration, vec};
+use poise::{serenity_prelude as serenity, Bucket, Command};
+use std::{collections::HashMap, env::var, sync::Arc, time::Duration, vec};
 // Types used by all command functions
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
 // Custom user data passed to all command functions
+#[derive(Debug)]
 pub struct Data {}
 
 /// A Group with one command
@@ -13,7 +14,7 @@ struct TestOneCommand {}
 #[poise::group(category = "One")]
 impl TestOneCommand {
     /// Say hello
-    #[poise::command(slash_command, prefix_command, rename = "hello")]
+    #[poise::command(slash_command, prefix_command, rename = "hello", bucket = "heavy")]
     async fn say_hello(ctx: Context<'_>) -> Result<(), Error> {
         let name = ctx.author();
         ctx.say(format!("Hello, {}", name)).await?;
@@ -21,10 +22,18 @@ impl TestOneCommand {
     }
 }
 
-/// A Group with multiple commands
+/// A Group with multiple commands, restricted to admins, exposed as `/math` subcommands
 struct TestMultipleCommands {}
 
-#[poise::group(category = "Multiple")]
+#[poise::group(
+    category = "Multiple",
+    check = "is_admin",
+    on_error = "group_err",
+    default_member_permissions = "MANAGE_GUILD",
+    guild_only,
+    slash_subcommands,
+    rename = "math"
+)]
 impl TestMultipleCommands {
     /// Add one to a number
     #[poise::command(slash_command, prefix_command, rename = "plus")]
@@ -43,6 +52,35 @@ impl TestMultipleCommands {
     }
 }
 
+/// Show this help menu, organized by the `category` declared on each `#[poise::group]`
+#[poise::command(slash_command, prefix_command, track_edits)]
+async fn help(
+    ctx: Context<'_>,
+    #[description = "Specific command to show help about"] command: Option<String>,
+) -> Result<(), Error> {
+    let config = poise::builtins::HelpConfiguration {
+        ephemeral: true,
+        hide_if_check_fails_silently: false,
+        category_order: vec!["One".into(), "Multiple".into()],
+        ..Default::default()
+    };
+    poise::builtins::help(ctx, command.as_deref(), config).await?;
+    Ok(())
+}
+
+/// Group-wide check for `TestMultipleCommands`: only admins may run these commands
+async fn is_admin(ctx: Context<'_>) -> Result<bool, Error> {
+    Ok(ctx
+        .author_member()
+        .await
+        .is_some_and(|member| member.permissions.is_some_and(|perms| perms.administrator())))
+}
+
+/// Group-wide error handler for `TestMultipleCommands`
+async fn group_err(error: poise::FrameworkError<'_, Data, Error>) {
+    eprintln!("Error in `TestMultipleCommands` group: {:?}", error);
+}
+
 // Handlers
 async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
     // This is our custom error handler
@@ -53,6 +91,18 @@ async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
         poise::FrameworkError::Command { error, ctx, .. } => {
             eprintln!("Error in command `{}`: {:?}", ctx.command().name, error,);
         }
+        poise::FrameworkError::CooldownHit {
+            remaining_cooldown,
+            ctx,
+            ..
+        } => {
+            let _ = ctx
+                .say(format!(
+                    "You're on cooldown, try again in {:.1}s",
+                    remaining_cooldown.as_secs_f64()
+                ))
+                .await;
+        }
         error => {
             if let Err(e) = poise::builtins::on_error(error).await {
                 eprintln!("Error while handling error: {}", e)
@@ -66,10 +116,8 @@ async fn main() {
     // FrameworkOptions contains all of poise's configuration option in one struct
     // Every option can be omitted to use its default value
     // println!("{:#?}", Test::commands());
-    let commands: Vec<Command<Data, Error>> = TestOneCommand::commands()
-        .into_iter()
-        .chain(TestMultipleCommands::commands().into_iter())
-        .collect();
+    let commands: Vec<Command<Data, Error>> =
+        poise::commands![TestOneCommand, TestMultipleCommands, help()];
 
     let options = poise::FrameworkOptions {
         commands,
@@ -78,6 +126,9 @@ async fn main() {
             edit_tracker: Some(Arc::new(poise::EditTracker::for_timespan(
                 Duration::from_secs(3600),
             ))),
+            // Lets "--plus 5" reach TestMultipleCommands::add_one directly, in addition to
+            // the full "--math plus 5" path the slash_subcommands parent command exposes
+            flatten_subcommands: true,
             ..Default::default()
         },
         // The global error handler for all error cases that may occur
@@ -106,6 +157,15 @@ async fn main() {
         // Enforce command checks even for owners (enforced by default)
         // Set to true to bypass checks, which is useful for testing
         skip_checks_for_owners: false,
+        // Named buckets that commands can opt into via #[poise::command(bucket = "...")]
+        buckets: HashMap::from([(
+            "heavy".to_string(),
+            Bucket::new()
+                .delay(Duration::from_secs(2))
+                .time_span(Duration::from_secs(60))
+                .limit(3)
+                .scope(poise::BucketScope::User),
+        )]),
         event_handler: |_ctx, event, _framework, _data| {
             Box::pin(async move {
                 println!(
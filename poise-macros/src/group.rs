@@ -0,0 +1,195 @@
+//! Implements `#[poise::group]`, which collects the `#[poise::command]`-annotated methods of an
+//! `impl` block into a [`poise::CommandGroup`] and applies group-wide defaults (checks, error
+//! handler, permissions, ...) to each of them.
+
+use crate::command::{extract_help_from_doc_comments, permissions_to_tokens};
+use proc_macro::TokenStream;
+use syn::spanned::Spanned as _;
+
+/// Representation of the group attribute arguments (`#[poise::group(...)]`)
+#[derive(Default, Debug, darling::FromMeta)]
+#[darling(default)]
+pub struct GroupArgs {
+    category: Option<String>,
+    #[darling(multiple)]
+    check: Vec<syn::Path>,
+    on_error: Option<syn::Path>,
+    default_member_permissions: Option<syn::punctuated::Punctuated<syn::Ident, syn::Token![|]>>,
+    owners_only: bool,
+    guild_only: bool,
+    dm_only: bool,
+    nsfw_only: bool,
+    rename: Option<String>,
+    /// Instead of returning the group's commands as a flat list, nest them as slash subcommands
+    /// under a single synthesized parent command (named after [`Self::rename`], or the `impl`
+    /// block's type otherwise).
+    slash_subcommands: bool,
+}
+
+/// The final path segment of a type, e.g. `Foo` for both `Foo` and `some::module::Foo`. Used as
+/// the fallback name for a synthesized `slash_subcommands` parent command.
+fn type_name(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last().map(|s| &s.ident),
+        _ => None,
+    }
+}
+
+/// Name of a `#[poise::command]`-annotated method, as it will be callable once the command macro
+/// has turned it into a zero-argument associated function returning a `Command`.
+fn command_method_idents(item_impl: &syn::ItemImpl) -> Vec<&syn::Ident> {
+    item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::ImplItem::Fn(method) => Some(method),
+            _ => None,
+        })
+        .filter(|method| {
+            method
+                .attrs
+                .iter()
+                .any(|attr| attr.path().segments.last().is_some_and(|s| s.ident == "command"))
+        })
+        .map(|method| &method.sig.ident)
+        .collect()
+}
+
+pub fn group(args: GroupArgs, item_impl: syn::ItemImpl) -> Result<TokenStream, darling::Error> {
+    let self_ty = &item_impl.self_ty;
+
+    let command_idents = command_method_idents(&item_impl);
+    let Some(first_command) = command_idents.first() else {
+        let err_msg = "#[poise::group] requires at least one #[poise::command] method";
+        return Err(syn::Error::new(item_impl.span(), err_msg).into());
+    };
+
+    // Determine U and E the same way the command macro does: by looking at the Context type of
+    // one of the group's own commands, before it's rewritten by its own #[poise::command] expansion
+    let first_command_fn = item_impl
+        .items
+        .iter()
+        .find_map(|item| match item {
+            syn::ImplItem::Fn(method) if method.sig.ident == **first_command => Some(method),
+            _ => None,
+        })
+        .expect("first_command_fn was just collected from this impl block");
+    let ctx_type = match first_command_fn.sig.inputs.first() {
+        Some(syn::FnArg::Typed(syn::PatType { ty, .. })) => &**ty,
+        _ => {
+            let err_msg = "expected a Context parameter";
+            return Err(syn::Error::new(first_command_fn.sig.span(), err_msg).into());
+        }
+    };
+    let ctx_type_with_static =
+        syn::fold::fold_type(&mut crate::util::AllLifetimesToStatic, ctx_type.clone());
+
+    let (description, _) = extract_help_from_doc_comments(&item_impl.attrs);
+    let description = crate::util::wrap_option_to_string(description.as_ref());
+
+    let category = crate::util::wrap_option_to_string(args.category.as_ref());
+    let default_member_permissions = permissions_to_tokens(&args.default_member_permissions);
+    let owners_only = args.owners_only;
+    let guild_only = args.guild_only;
+    let dm_only = args.dm_only;
+    let nsfw_only = args.nsfw_only;
+    let checks = &args.check;
+    let on_error = match &args.on_error {
+        Some(on_error) => quote::quote! { Some(|err| Box::pin(#on_error(err))) },
+        None => quote::quote! { None },
+    };
+
+    let commands_vec = quote::quote! {
+        vec![ #( apply_group_defaults(#self_ty::#command_idents()) ),* ]
+    };
+
+    // Either return the (defaulted) commands as a flat list, or nest them as slash subcommands
+    // under a single synthesized parent command, depending on `slash_subcommands`
+    let commands_expr = if args.slash_subcommands {
+        let parent_name = match &args.rename {
+            Some(rename) => rename.clone(),
+            None => match type_name(self_ty) {
+                Some(ident) => ident.to_string(),
+                None => {
+                    let err_msg = "#[poise::group(slash_subcommands)] requires either `rename` \
+                        or a named `impl` type to derive the parent command name from";
+                    return Err(syn::Error::new(item_impl.span(), err_msg).into());
+                }
+            },
+        };
+
+        quote::quote! {
+            vec![::poise::Command {
+                // Never actually invoked: Discord requires a SubCommand option whenever the
+                // parent has subcommands, so dispatch always resolves to a leaf action instead.
+                // Still needs to be Some(..) though, or Command::create_as_slash_command bails
+                // out and the parent (and therefore its subcommands) never gets registered.
+                slash_action: Some(|ctx| Box::pin(async move {
+                    Err(::poise::FrameworkError::SubcommandRequired { ctx: ctx.into() })
+                })),
+                subcommands: #commands_vec,
+                subcommand_required: true,
+                name: String::from(#parent_name),
+                qualified_name: String::from(#parent_name),
+                category: #category,
+                description: #description,
+                guild_only: #guild_only,
+                dm_only: #dm_only,
+                nsfw_only: #nsfw_only,
+                owners_only: #owners_only,
+                default_member_permissions: #default_member_permissions,
+                on_error: #on_error,
+                checks: vec![ #( |ctx| Box::pin(#checks(ctx)) ),* ],
+                ..Default::default()
+            }]
+        }
+    } else {
+        commands_vec
+    };
+
+    Ok(TokenStream::from(quote::quote! {
+        #item_impl
+
+        impl ::poise::CommandGroup<
+            <#ctx_type_with_static as poise::_GetGenerics>::U,
+            <#ctx_type_with_static as poise::_GetGenerics>::E,
+        > for #self_ty {
+            fn commands() -> Vec<::poise::Command<
+                <#ctx_type_with_static as poise::_GetGenerics>::U,
+                <#ctx_type_with_static as poise::_GetGenerics>::E,
+            >> {
+                fn apply_group_defaults(
+                    mut cmd: ::poise::Command<
+                        <#ctx_type_with_static as poise::_GetGenerics>::U,
+                        <#ctx_type_with_static as poise::_GetGenerics>::E,
+                    >,
+                ) -> ::poise::Command<
+                    <#ctx_type_with_static as poise::_GetGenerics>::U,
+                    <#ctx_type_with_static as poise::_GetGenerics>::E,
+                > {
+                    if cmd.category.is_none() {
+                        cmd.category = #category;
+                    }
+                    cmd.owners_only = cmd.owners_only || #owners_only;
+                    cmd.guild_only = cmd.guild_only || #guild_only;
+                    cmd.dm_only = cmd.dm_only || #dm_only;
+                    cmd.nsfw_only = cmd.nsfw_only || #nsfw_only;
+                    if cmd.default_member_permissions.is_empty() {
+                        cmd.default_member_permissions = #default_member_permissions;
+                    }
+                    if cmd.on_error.is_none() {
+                        cmd.on_error = #on_error;
+                    }
+                    let mut group_checks: ::std::vec::Vec<
+                        fn(::poise::Context<'_, _, _>) -> ::poise::BoxFuture<'_, ::std::result::Result<bool, _>>,
+                    > = vec![ #( |ctx| Box::pin(#checks(ctx)) ),* ];
+                    group_checks.append(&mut cmd.checks);
+                    cmd.checks = group_checks;
+                    cmd
+                }
+
+                #commands_expr
+            }
+        }
+    }))
+}
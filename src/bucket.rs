@@ -0,0 +1,257 @@
+//! Infrastructure for named, shared rate-limit buckets (see [`crate::FrameworkOptions::buckets`])
+
+use crate::serenity_prelude as serenity;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Determines which invocations count against the same [`Bucket`]'s limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum BucketScope {
+    /// All invocations, across every user, guild and channel, share the same limit.
+    Global,
+    /// Invocations are limited per user.
+    #[default]
+    User,
+    /// Invocations are limited per guild. Has no effect in DMs.
+    Guild,
+    /// Invocations are limited per channel.
+    Channel,
+    /// Invocations are limited per guild member (user and guild). Has no effect in DMs.
+    Member,
+}
+
+/// Key under which invocation timestamps are grouped, derived from a [`crate::CooldownContext`]
+/// according to a command's [`BucketScope`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum ScopeKey {
+    /// See [`BucketScope::Global`]
+    Global,
+    /// See [`BucketScope::User`]
+    User(serenity::UserId),
+    /// See [`BucketScope::Guild`]
+    Guild(serenity::GuildId),
+    /// See [`BucketScope::Channel`]
+    Channel(serenity::ChannelId),
+    /// See [`BucketScope::Member`]
+    Member(serenity::UserId, serenity::GuildId),
+}
+
+impl BucketScope {
+    /// Derives the grouping key for `ctx` under this scope. Returns `None` if this scope doesn't
+    /// apply in `ctx` (i.e. a guild-based scope outside of a guild), meaning no limit is enforced.
+    fn key(self, ctx: &crate::CooldownContext) -> Option<ScopeKey> {
+        Some(match self {
+            Self::Global => ScopeKey::Global,
+            Self::User => ScopeKey::User(ctx.user_id),
+            Self::Guild => ScopeKey::Guild(ctx.guild_id?),
+            Self::Channel => ScopeKey::Channel(ctx.channel_id),
+            Self::Member => ScopeKey::Member(ctx.user_id, ctx.guild_id?),
+        })
+    }
+}
+
+/// A shared rate limit that multiple commands can opt into via [`crate::Command::bucket`],
+/// consuming from the same limit rather than each tracking their own cooldown.
+///
+/// Two conditions gate invocations: a minimum [`Self::delay`] between any two invocations in the
+/// same scope, and a cap of [`Self::limit`] invocations per rolling [`Self::time_span`] window.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// let bucket = poise::Bucket::new()
+///     .delay(Duration::from_secs(2))
+///     .time_span(Duration::from_secs(60))
+///     .limit(3)
+///     .scope(poise::BucketScope::User);
+/// ```
+#[derive(Debug)]
+pub struct Bucket {
+    /// Minimum duration between two invocations in the same scope
+    delay: Duration,
+    /// Duration of the rolling window over which [`Self::limit`] is enforced
+    time_span: Duration,
+    /// Maximum number of invocations allowed within [`Self::time_span`]
+    limit: usize,
+    /// Which invocations are grouped together, see [`BucketScope`]
+    scope: BucketScope,
+    /// Invocation timestamps recorded so far, per scope key
+    invocations: Mutex<HashMap<ScopeKey, VecDeque<Instant>>>,
+}
+
+impl Default for Bucket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bucket {
+    /// Creates a new bucket with no delay, an unbounded time span and a limit of 1, scoped per
+    /// user. Use the builder methods to configure it.
+    pub fn new() -> Self {
+        Self {
+            delay: Duration::ZERO,
+            time_span: Duration::ZERO,
+            limit: 1,
+            scope: BucketScope::User,
+            invocations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the minimum duration that must pass between two invocations in the same scope
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the duration of the rolling window over which [`Self::limit`] is enforced
+    pub fn time_span(mut self, time_span: Duration) -> Self {
+        self.time_span = time_span;
+        self
+    }
+
+    /// Sets the maximum number of invocations allowed within [`Self::time_span`]
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = limit.max(1) as usize;
+        self
+    }
+
+    /// Sets which invocations are grouped together, see [`BucketScope`]
+    pub fn scope(mut self, scope: BucketScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// An unbounded (zero) [`Self::time_span`] means no rolling-window cap is configured:
+    /// `time_span` is independent of `delay`, so a delay-only bucket shouldn't have its history
+    /// pruned away every call, which would otherwise defeat the delay gate below.
+    fn has_time_span(&self) -> bool {
+        !self.time_span.is_zero()
+    }
+
+    /// Discards timestamps that have fallen out of the rolling [`Self::time_span`] window. A
+    /// no-op when [`Self::has_time_span`] is false.
+    fn prune(&self, timestamps: &mut VecDeque<Instant>, now: Instant) {
+        if self.has_time_span() {
+            while matches!(
+                timestamps.front(),
+                Some(&oldest) if now.saturating_duration_since(oldest) >= self.time_span
+            ) {
+                timestamps.pop_front();
+            }
+        }
+    }
+
+    /// Queries this bucket and checks if `ctx` is currently allowed to invoke. If not, returns
+    /// the remaining time until the next invocation will be allowed. Doesn't record anything;
+    /// the caller should call [`Self::start_cooldown`] once argument parsing succeeds, mirroring
+    /// [`crate::CooldownTracker::remaining_cooldown`]/[`crate::CooldownTracker::start_cooldown`].
+    pub fn remaining_cooldown(&self, ctx: &crate::CooldownContext) -> Option<Duration> {
+        // This scope doesn't apply here (e.g. a guild-based bucket used in a DM), so there's
+        // nothing to rate-limit against
+        let key = self.scope.key(ctx)?;
+
+        let mut invocations = self.invocations.lock().unwrap();
+        let timestamps = invocations.entry(key).or_default();
+
+        let now = Instant::now();
+        self.prune(timestamps, now);
+
+        if let Some(&last) = timestamps.back() {
+            let since_last = now.saturating_duration_since(last);
+            if since_last < self.delay {
+                return Some(self.delay - since_last);
+            }
+        }
+
+        if self.has_time_span() && timestamps.len() >= self.limit {
+            let oldest = *timestamps.front().expect("limit is always at least 1");
+            return Some(
+                self.time_span
+                    .saturating_sub(now.saturating_duration_since(oldest)),
+            );
+        }
+
+        None
+    }
+
+    /// Indicates that an invocation in `ctx` has happened and should count against this bucket's
+    /// [`Self::delay`] and [`Self::limit`] going forward.
+    pub fn start_cooldown(&self, ctx: &crate::CooldownContext) {
+        let Some(key) = self.scope.key(ctx) else {
+            // This scope doesn't apply here (e.g. a guild-based bucket used in a DM), so there's
+            // nothing to record
+            return;
+        };
+
+        let mut invocations = self.invocations.lock().unwrap();
+        let timestamps = invocations.entry(key).or_default();
+
+        let now = Instant::now();
+        self.prune(timestamps, now);
+
+        if !self.has_time_span() {
+            // No window to prune against later; keep only what `delay` needs next time
+            timestamps.clear();
+        }
+        timestamps.push_back(now);
+    }
+}
+
+#[cfg(test)]
+fn test_ctx() -> crate::CooldownContext {
+    crate::CooldownContext {
+        user_id: serenity::UserId::new(1),
+        guild_id: None,
+        channel_id: serenity::ChannelId::new(1),
+    }
+}
+
+#[test]
+fn test_bucket_delay_only_survives_repeated_invocations() {
+    // A bucket with only `.delay()` set (no `.time_span()`) should keep gating every invocation
+    // by the delay, not just the first one
+    let bucket = Bucket::new().delay(Duration::from_millis(50));
+    let ctx = test_ctx();
+
+    assert_eq!(bucket.remaining_cooldown(&ctx), None);
+    bucket.start_cooldown(&ctx);
+
+    assert!(bucket.remaining_cooldown(&ctx).is_some());
+    std::thread::sleep(Duration::from_millis(60));
+    assert_eq!(bucket.remaining_cooldown(&ctx), None);
+    bucket.start_cooldown(&ctx);
+
+    assert!(bucket.remaining_cooldown(&ctx).is_some());
+}
+
+#[test]
+fn test_bucket_limit_within_time_span() {
+    let bucket = Bucket::new().time_span(Duration::from_secs(60)).limit(2);
+    let ctx = test_ctx();
+
+    assert_eq!(bucket.remaining_cooldown(&ctx), None);
+    bucket.start_cooldown(&ctx);
+
+    assert_eq!(bucket.remaining_cooldown(&ctx), None);
+    bucket.start_cooldown(&ctx);
+
+    // Limit of 2 reached within the time span: third invocation should be rejected
+    assert!(bucket.remaining_cooldown(&ctx).is_some());
+}
+
+#[test]
+fn test_bucket_scope_isolates_invocations() {
+    let bucket = Bucket::new().delay(Duration::from_secs(60));
+    let ctx_a = test_ctx();
+    let ctx_b = crate::CooldownContext {
+        user_id: serenity::UserId::new(2),
+        ..test_ctx()
+    };
+
+    bucket.start_cooldown(&ctx_a);
+
+    // A different user in the same scope shouldn't be affected by ctx_a's invocation
+    assert_eq!(bucket.remaining_cooldown(&ctx_b), None);
+    assert!(bucket.remaining_cooldown(&ctx_a).is_some());
+}
@@ -0,0 +1,13 @@
+//! Application command argument handling code
+
+mod slash_macro;
+pub use slash_macro::*;
+
+mod slash_trait;
+pub use slash_trait::*;
+
+mod context_menu;
+pub use context_menu::*;
+
+mod into_stream;
+pub use into_stream::*;